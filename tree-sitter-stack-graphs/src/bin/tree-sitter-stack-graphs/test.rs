@@ -15,8 +15,11 @@ use stack_graphs::graph::File;
 use stack_graphs::graph::StackGraph;
 use stack_graphs::json::Filter;
 use stack_graphs::paths::Paths;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Mutex;
 use tree_sitter_graph::Variables;
 use tree_sitter_stack_graphs::loader::Loader;
 use tree_sitter_stack_graphs::test::Test;
@@ -47,6 +50,63 @@ impl OutputMode {
     }
 }
 
+/// Format for the machine-readable test report written via `--report`.
+#[derive(Copy, Clone, PartialEq, Eq, ArgEnum)]
+pub enum ReportFormat {
+    Json,
+    Junit,
+}
+
+impl ReportFormat {
+    fn default_report_path(&self) -> PathBuf {
+        match self {
+            Self::Json => PathBuf::from("test-report.json"),
+            Self::Junit => PathBuf::from("test-report.xml"),
+        }
+    }
+}
+
+/// Structured record of a single test's outcome, accumulated while tests run so
+/// that a machine-readable report can be written once the run is complete.
+struct TestReportEntry {
+    path: PathBuf,
+    ignored: bool,
+    success: bool,
+    success_count: usize,
+    total_count: usize,
+    failures: Vec<String>,
+    saved_graph: Option<PathBuf>,
+    saved_paths: Option<PathBuf>,
+    saved_visualization: Option<PathBuf>,
+}
+
+impl TestReportEntry {
+    fn ignored(path: &Path) -> Self {
+        Self {
+            path: path.to_path_buf(),
+            ignored: true,
+            success: true,
+            success_count: 0,
+            total_count: 0,
+            failures: Vec::new(),
+            saved_graph: None,
+            saved_paths: None,
+            saved_visualization: None,
+        }
+    }
+}
+
+/// Paths of any artifacts written or checked by [`Command::save_output`], along
+/// with any snapshot failures it found, used to populate the corresponding
+/// [`TestReportEntry`] fields.
+#[derive(Default)]
+struct SavedArtifacts {
+    failures: Vec<String>,
+    graph: Option<PathBuf>,
+    paths: Option<PathBuf>,
+    visualization: Option<PathBuf>,
+}
+
 /// Run tests
 #[derive(clap::Parser)]
 #[clap(after_help = r#"PATH SPECIFICATIONS:
@@ -132,41 +192,225 @@ pub struct Command {
     /// Controls when graphs, paths, or visualization are saved.
     #[clap(long, arg_enum, default_value_t = OutputMode::OnFailure)]
     output_mode: OutputMode,
+
+    /// Run tests in parallel using the given number of worker threads, each with
+    /// its own loader. Defaults to running tests sequentially on the main thread.
+    #[clap(long, short = 'j', value_name = "JOBS")]
+    jobs: Option<usize>,
+
+    /// Compare saved graph/paths output against the existing expected snapshot
+    /// file (resolved through the same path specification) instead of
+    /// unconditionally writing fresh output. A mismatch is reported as a test
+    /// failure, with a unified diff between the expected and actual output.
+    #[clap(long)]
+    snapshot: bool,
+
+    /// Update expected snapshot files with the current output instead of failing
+    /// on mismatch. Implies --snapshot.
+    #[clap(long, alias = "update")]
+    bless: bool,
+
+    /// Write a machine-readable test report in the given format, for consumption
+    /// by CI systems. Can be combined freely with the normal console output.
+    #[clap(long, arg_enum, value_name = "FORMAT")]
+    report: Option<ReportFormat>,
+
+    /// Output path for the test report requested via --report.
+    /// [default: test-report.json or test-report.xml, depending on --report]
+    #[clap(long, value_name = "PATH", value_hint = ValueHint::AnyPath)]
+    report_output: Option<PathBuf>,
+
+    /// Only run tests whose path relative to the test root contains this
+    /// substring. Can be repeated; a test matching any filter is run.
+    #[clap(long, value_name = "SUBSTRING")]
+    filter: Vec<String>,
+
+    /// Skip tests whose path relative to the test root contains this substring.
+    /// Can be repeated; a test matching any skip pattern is skipped.
+    #[clap(long, value_name = "SUBSTRING")]
+    skip: Vec<String>,
+
+    /// Match --filter/--skip patterns against the whole relative path exactly,
+    /// instead of as substrings.
+    #[clap(long)]
+    exact: bool,
+
+    /// List discovered test files, after applying --filter/--skip, without
+    /// running them.
+    #[clap(long)]
+    list: bool,
 }
 
 impl Command {
     pub fn run(&self) -> anyhow::Result<()> {
-        let mut loader = self.loader.new_loader()?;
-        let mut total_failure_count = 0;
+        let (descriptors, excluded) = self.select_test_paths();
+
+        if self.show_ignored {
+            for (_, test_path) in &excluded {
+                println!("{} {}", "⦵".dimmed(), test_path.display());
+            }
+        }
+
+        if self.list {
+            for (_, test_path) in &descriptors {
+                println!("{}", test_path.display());
+            }
+            return Ok(());
+        }
+
+        let (total_failure_count, mut report_entries) = match self.jobs {
+            Some(jobs) if jobs > 1 => self.run_parallel(descriptors, jobs)?,
+            _ => self.run_sequential(descriptors)?,
+        };
+        // Sort so the report is deterministic regardless of discovery order or
+        // --jobs: sequential execution preserves discovery order, while parallel
+        // execution already returns entries sorted by test path.
+        report_entries.sort_by(|a, b| a.path.cmp(&b.path));
+
+        if let Some(format) = self.report {
+            let report_path = self
+                .report_output
+                .clone()
+                .unwrap_or_else(|| format.default_report_path());
+            self.write_report(format, &report_path, &report_entries)?;
+        }
+
+        if total_failure_count > 0 {
+            return Err(anyhow!(
+                "{} assertion{} failed",
+                total_failure_count,
+                if total_failure_count == 1 { "" } else { "s" }
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Walk all test arguments up front into a flat list of (test root, test path)
+    /// descriptors, so that both sequential and parallel execution share the same
+    /// test discovery.
+    fn collect_test_paths(&self) -> Vec<(PathBuf, PathBuf)> {
+        let mut descriptors = Vec::new();
         for test_path in &self.tests {
             if test_path.is_dir() {
-                let test_root = test_path;
+                let test_root = test_path.clone();
                 for test_entry in WalkDir::new(test_path)
                     .follow_links(true)
                     .into_iter()
                     .filter_map(|e| e.ok())
                     .filter(|e| e.file_type().is_file())
                 {
-                    let test_path = test_entry.path();
-                    total_failure_count +=
-                        self.run_test_with_context(test_root, test_path, &mut loader)?;
+                    descriptors.push((test_root.clone(), test_entry.path().to_path_buf()));
                 }
             } else {
-                let test_root = test_path.parent().unwrap();
-                total_failure_count +=
-                    self.run_test_with_context(test_root, test_path, &mut loader)?;
+                let test_root = test_path.parent().unwrap().to_path_buf();
+                descriptors.push((test_root, test_path.clone()));
             }
         }
+        descriptors
+    }
 
-        if total_failure_count > 0 {
-            return Err(anyhow!(
-                "{} assertion{} failed",
-                total_failure_count,
-                if total_failure_count == 1 { "" } else { "s" }
-            ));
+    /// Discover all test descriptors and partition them into those selected by
+    /// `--filter`/`--skip` and those excluded, so excluded tests can be surfaced
+    /// with `--show-ignored` without being run. The number of excluded tests is
+    /// not printed or reported.
+    fn select_test_paths(&self) -> (Vec<(PathBuf, PathBuf)>, Vec<(PathBuf, PathBuf)>) {
+        self.collect_test_paths()
+            .into_iter()
+            .partition(|(test_root, test_path)| {
+                let relative_path = test_path.strip_prefix(test_root).unwrap_or(test_path);
+                self.is_selected(relative_path)
+            })
+    }
+
+    /// Test whether a test's path relative to its test root is selected by the
+    /// configured `--filter`/`--skip` patterns (matched with `--exact` or, by
+    /// default, as substrings).
+    fn is_selected(&self, relative_path: &Path) -> bool {
+        let relative_path = relative_path.to_string_lossy();
+        let matches_pattern = |pattern: &str| {
+            if self.exact {
+                relative_path == pattern
+            } else {
+                relative_path.contains(pattern)
+            }
+        };
+        if !self.filter.is_empty() && !self.filter.iter().any(|p| matches_pattern(p)) {
+            return false;
         }
+        !self.skip.iter().any(|p| matches_pattern(p))
+    }
 
-        Ok(())
+    /// Run tests one after another on the main thread, using a single loader.
+    /// Output is printed as soon as each test finishes, in discovery order.
+    fn run_sequential(
+        &self,
+        descriptors: Vec<(PathBuf, PathBuf)>,
+    ) -> anyhow::Result<(usize, Vec<TestReportEntry>)> {
+        let mut loader = self.loader.new_loader()?;
+        let mut total_failure_count = 0;
+        let mut report_entries = Vec::new();
+        for (test_root, test_path) in &descriptors {
+            let (failure_count, output, entry) =
+                self.run_test_with_context(test_root, test_path, &mut loader)?;
+            print!("{}", output);
+            total_failure_count += failure_count;
+            report_entries.push(entry);
+        }
+        Ok((total_failure_count, report_entries))
+    }
+
+    /// Run tests across `jobs` worker threads. Each worker builds its own loader
+    /// from the shared `LoaderArgs`, since `Loader` cannot be shared across threads,
+    /// and pulls test descriptors from a shared work queue until it is empty. Output
+    /// is buffered per test and flushed by the main thread once all workers are
+    /// done, sorted by test path, so that concurrent runs don't interleave output.
+    fn run_parallel(
+        &self,
+        descriptors: Vec<(PathBuf, PathBuf)>,
+        jobs: usize,
+    ) -> anyhow::Result<(usize, Vec<TestReportEntry>)> {
+        let queue = Mutex::new(descriptors.into_iter().collect::<VecDeque<_>>());
+        let results: Mutex<Vec<(PathBuf, anyhow::Result<(usize, String, TestReportEntry)>)>> =
+            Mutex::new(Vec::new());
+
+        std::thread::scope(|scope| -> anyhow::Result<()> {
+            let handles: Vec<_> = (0..jobs)
+                .map(|_| {
+                    scope.spawn(|| -> anyhow::Result<()> {
+                        let mut loader = self.loader.new_loader()?;
+                        loop {
+                            let next = queue.lock().unwrap().pop_front();
+                            let (test_root, test_path) = match next {
+                                Some(next) => next,
+                                None => break,
+                            };
+                            let result =
+                                self.run_test_with_context(&test_root, &test_path, &mut loader);
+                            results.lock().unwrap().push((test_path, result));
+                        }
+                        Ok(())
+                    })
+                })
+                .collect();
+            for handle in handles {
+                handle.join().expect("test worker thread panicked")?;
+            }
+            Ok(())
+        })?;
+
+        let mut results = results.into_inner().unwrap();
+        results.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut total_failure_count = 0;
+        let mut report_entries = Vec::new();
+        for (_, result) in results {
+            let (failure_count, output, entry) = result?;
+            print!("{}", output);
+            total_failure_count += failure_count;
+            report_entries.push(entry);
+        }
+        Ok((total_failure_count, report_entries))
     }
 
     /// Run test file and add error context to any failures that are returned.
@@ -175,26 +419,29 @@ impl Command {
         test_root: &Path,
         test_path: &Path,
         loader: &mut Loader,
-    ) -> anyhow::Result<usize> {
+    ) -> anyhow::Result<(usize, String, TestReportEntry)> {
         self.run_test(test_root, test_path, loader)
             .with_context(|| format!("Error running test {}", test_path.display()))
     }
 
-    /// Run test file.
+    /// Run test file. Returns the failure count and the output that would have
+    /// been printed for this test, so that callers can buffer and order output
+    /// across parallel workers, together with a structured report entry.
     fn run_test(
         &self,
         test_root: &Path,
         test_path: &Path,
         loader: &mut Loader,
-    ) -> anyhow::Result<usize> {
+    ) -> anyhow::Result<(usize, String, TestReportEntry)> {
+        let mut output = String::new();
         let source = std::fs::read_to_string(test_path)?;
         let sgl = match loader.load_for_file(test_path, Some(&source), &NoCancellation)? {
             Some(sgl) => sgl,
             None => {
                 if self.show_ignored {
-                    println!("{} {}", "⦵".dimmed(), test_path.display());
+                    writeln!(output, "{} {}", "⦵".dimmed(), test_path.display()).unwrap();
                 }
-                return Ok(0);
+                return Ok((0, output, TestReportEntry::ignored(test_path)));
             }
         };
         let default_fragment_path = test_path.strip_prefix(test_root).unwrap();
@@ -223,19 +470,53 @@ impl Command {
             )?;
         }
         let result = test.run(&NoCancellation)?;
-        let success = self.handle_result(test_path, &result)?;
-        if self.output_mode.test(!success) {
+        let success = self.handle_result(test_path, &result, &mut output)?;
+        let mut saved = SavedArtifacts::default();
+        if self.effective_output_mode().test(!success) {
             let files = test.fragments.iter().map(|f| f.file).collect::<Vec<_>>();
-            self.save_output(
+            saved = self.save_output(
                 test_root,
                 test_path,
                 &test.graph,
                 &mut test.paths,
                 &|_: &StackGraph, h: &Handle<File>| files.contains(h),
                 success,
+                &mut output,
             )?;
         }
-        Ok(result.failure_count())
+        let mut failures = result
+            .failures_iter()
+            .map(|f| f.to_string())
+            .collect::<Vec<_>>();
+        failures.extend(saved.failures.iter().cloned());
+        let entry = TestReportEntry {
+            path: test_path.to_path_buf(),
+            ignored: false,
+            success: failures.is_empty(),
+            success_count: result.success_count(),
+            total_count: result.count(),
+            failures,
+            saved_graph: saved.graph.clone(),
+            saved_paths: saved.paths.clone(),
+            saved_visualization: saved.visualization.clone(),
+        };
+        Ok((
+            result.failure_count() + saved.failures.len(),
+            output,
+            entry,
+        ))
+    }
+
+    /// The output mode that actually governs whether `save_output` runs.
+    /// `--snapshot`/`--bless` force `Always`: snapshot regressions must be caught
+    /// on every run, not just on runs where assertions already failed, otherwise
+    /// the snapshot would never be checked (or blessed) for a passing test.
+    fn effective_output_mode(&self) -> OutputMode {
+        if self.snapshot || self.bless {
+            OutputMode::Always
+        } else {
+            self.output_mode
+        }
     }
 
     fn load_builtins_into(
@@ -267,20 +548,26 @@ impl Command {
         }
     }
 
-    fn handle_result(&self, test_path: &Path, result: &TestResult) -> anyhow::Result<bool> {
+    fn handle_result(
+        &self,
+        test_path: &Path,
+        result: &TestResult,
+        output: &mut String,
+    ) -> anyhow::Result<bool> {
         let success = result.failure_count() == 0;
         if !success || !self.hide_passing {
-            println!(
+            writeln!(
+                output,
                 "{} {}: {}/{} assertions",
                 if success { "✓".green() } else { "✗".red() },
                 test_path.display(),
                 result.success_count(),
                 result.count()
-            );
+            )?;
         }
         if !success && !self.hide_failure_errors {
             for failure in result.failures_iter() {
-                println!("  {}", failure);
+                writeln!(output, "  {}", failure)?;
             }
         }
         Ok(success)
@@ -294,26 +581,34 @@ impl Command {
         paths: &mut Paths,
         filter: &dyn Filter,
         success: bool,
-    ) -> anyhow::Result<()> {
+        output: &mut String,
+    ) -> anyhow::Result<SavedArtifacts> {
+        let mut saved = SavedArtifacts::default();
         if let Some(path) = self
             .save_graph
             .as_ref()
             .map(|spec| spec.format(test_root, test_path))
         {
-            self.save_graph(&path, &graph, filter)?;
-            if !success || !self.hide_passing {
-                println!("  Graph: {}", path.display());
+            let json = graph.to_json(filter).to_string_pretty()?;
+            if let Some(failure) =
+                self.save_or_check_snapshot(test_root, &path, &json, "Graph", success, output)?
+            {
+                saved.failures.push(failure);
             }
+            saved.graph = Some(path);
         }
         if let Some(path) = self
             .save_paths
             .as_ref()
             .map(|spec| spec.format(test_root, test_path))
         {
-            self.save_paths(&path, paths, graph, filter)?;
-            if !success || !self.hide_passing {
-                println!("  Paths: {}", path.display());
+            let json = paths.to_json(graph, filter).to_string_pretty()?;
+            if let Some(failure) =
+                self.save_or_check_snapshot(test_root, &path, &json, "Paths", success, output)?
+            {
+                saved.failures.push(failure);
             }
+            saved.paths = Some(path);
         }
         if let Some(path) = self
             .save_visualization
@@ -322,57 +617,320 @@ impl Command {
         {
             self.save_visualization(&path, paths, graph, filter, &test_path)?;
             if !success || !self.hide_passing {
-                println!("  Visualization: {}", path.display());
+                writeln!(output, "  Visualization: {}", path.display())?;
             }
+            saved.visualization = Some(path);
         }
-        Ok(())
+        Ok(saved)
     }
 
-    fn save_graph(
+    /// Either write `content` to `path` unconditionally (the default behavior), or,
+    /// in snapshot mode (`--snapshot`/`--bless`), compare it against the expected
+    /// file already at `path` and report a mismatch as a failure with a unified
+    /// diff. `--bless` overwrites the expected file with `content` (normalized,
+    /// so committed snapshots are already canonical) instead of comparing.
+    /// Returns a failure message if this check failed the test, so that callers
+    /// can fold it into both the console output and the structured test report.
+    fn save_or_check_snapshot(
         &self,
+        test_root: &Path,
         path: &Path,
-        graph: &StackGraph,
-        filter: &dyn Filter,
-    ) -> anyhow::Result<()> {
-        let json = graph.to_json(filter).to_string_pretty()?;
+        content: &str,
+        label: &str,
+        success: bool,
+        output: &mut String,
+    ) -> anyhow::Result<Option<String>> {
+        if !self.snapshot && !self.bless {
+            self.write_output(path, content)?;
+            if !success || !self.hide_passing {
+                writeln!(output, "  {}: {}", label, path.display())?;
+            }
+            return Ok(None);
+        }
+
+        if self.bless {
+            self.write_output(path, &normalize_snapshot(content, test_root))?;
+            writeln!(output, "  {}: {} (snapshot updated)", label, path.display())?;
+            return Ok(None);
+        }
+
+        let expected = match std::fs::read_to_string(path) {
+            Ok(expected) => expected,
+            Err(_) => {
+                let message = format!(
+                    "{} snapshot missing: {} (run with --bless to create it)",
+                    label,
+                    path.display()
+                );
+                writeln!(output, "  {}", message)?;
+                return Ok(Some(message));
+            }
+        };
+        let expected = normalize_snapshot(&expected, test_root);
+        let actual = normalize_snapshot(content, test_root);
+        if expected == actual {
+            if !self.hide_passing {
+                writeln!(output, "  {}: {} (snapshot matches)", label, path.display())?;
+            }
+            Ok(None)
+        } else {
+            let message = format!("{} snapshot mismatch: {}", label, path.display());
+            writeln!(output, "  {}", message)?;
+            write!(output, "{}", unified_line_diff(&expected, &actual))?;
+            Ok(Some(message))
+        }
+    }
+
+    fn write_output(&self, path: &Path, content: &str) -> anyhow::Result<()> {
         if let Some(dir) = path.parent() {
             std::fs::create_dir_all(dir)?;
         }
-        std::fs::write(&path, json)
-            .with_context(|| format!("Unable to write graph {}", path.display()))?;
-        Ok(())
+        std::fs::write(path, content)
+            .with_context(|| format!("Unable to write {}", path.display()))
     }
 
-    fn save_paths(
+    fn save_visualization(
         &self,
         path: &Path,
         paths: &mut Paths,
         graph: &StackGraph,
         filter: &dyn Filter,
+        test_path: &Path,
     ) -> anyhow::Result<()> {
-        let json = paths.to_json(graph, filter).to_string_pretty()?;
+        let html = graph.to_html_string(&format!("{}", test_path.display()), paths, filter)?;
         if let Some(dir) = path.parent() {
             std::fs::create_dir_all(dir)?;
         }
-        std::fs::write(&path, json)
+        std::fs::write(&path, html)
             .with_context(|| format!("Unable to write graph {}", path.display()))?;
         Ok(())
     }
 
-    fn save_visualization(
+    /// Write the accumulated per-test report entries to `path` in the given
+    /// format, independent of the normal console output produced while running.
+    fn write_report(
         &self,
+        format: ReportFormat,
         path: &Path,
-        paths: &mut Paths,
-        graph: &StackGraph,
-        filter: &dyn Filter,
-        test_path: &Path,
+        entries: &[TestReportEntry],
     ) -> anyhow::Result<()> {
-        let html = graph.to_html_string(&format!("{}", test_path.display()), paths, filter)?;
+        let content = match format {
+            ReportFormat::Json => render_json_report(entries),
+            ReportFormat::Junit => render_junit_report(entries),
+        };
         if let Some(dir) = path.parent() {
             std::fs::create_dir_all(dir)?;
         }
-        std::fs::write(&path, html)
-            .with_context(|| format!("Unable to write graph {}", path.display()))?;
+        std::fs::write(path, content)
+            .with_context(|| format!("Unable to write test report {}", path.display()))?;
+        println!("Report: {}", path.display());
         Ok(())
     }
 }
+
+/// Normalize volatile content before comparing snapshots, so that runs from
+/// different checkouts (or invoked with a different but equivalent test path)
+/// produce identical output. This replaces any occurrence of the current
+/// working directory and the canonicalized test root with a `.` placeholder.
+/// It does not attempt to normalize absolute paths outside of those two
+/// directories. It also does not reorder content: `to_json` serializes the
+/// graph/paths arenas in insertion order, which is already deterministic for
+/// a given test run, so no ordering normalization is performed here.
+fn normalize_snapshot(content: &str, test_root: &Path) -> String {
+    let mut normalized = content.to_string();
+    for dir in [std::env::current_dir().ok(), test_root.canonicalize().ok()] {
+        if let Some(dir) = dir.map(|dir| dir.to_string_lossy().into_owned()) {
+            if !dir.is_empty() {
+                normalized = normalized.replace(&dir, ".");
+            }
+        }
+    }
+    normalized
+}
+
+/// Compute a unified-style line diff between `expected` and `actual`, using the
+/// longest common subsequence of lines to find a minimal set of removals and
+/// insertions. Removed lines are colored red and prefixed with `-`, added lines
+/// are colored green and prefixed with `+`. Unchanged lines are not printed,
+/// and no hunk headers or line numbers are emitted.
+fn unified_line_diff(expected: &str, actual: &str) -> String {
+    let expected_lines = expected.lines().collect::<Vec<_>>();
+    let actual_lines = actual.lines().collect::<Vec<_>>();
+    let (n, m) = (expected_lines.len(), actual_lines.len());
+
+    let mut lcs_len = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs_len[i][j] = if expected_lines[i] == actual_lines[j] {
+                lcs_len[i + 1][j + 1] + 1
+            } else {
+                lcs_len[i + 1][j].max(lcs_len[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if expected_lines[i] == actual_lines[j] {
+            i += 1;
+            j += 1;
+        } else if lcs_len[i + 1][j] >= lcs_len[i][j + 1] {
+            let _ = writeln!(diff, "{}", format!("- {}", expected_lines[i]).red());
+            i += 1;
+        } else {
+            let _ = writeln!(diff, "{}", format!("+ {}", actual_lines[j]).green());
+            j += 1;
+        }
+    }
+    while i < n {
+        let _ = writeln!(diff, "{}", format!("- {}", expected_lines[i]).red());
+        i += 1;
+    }
+    while j < m {
+        let _ = writeln!(diff, "{}", format!("+ {}", actual_lines[j]).green());
+        j += 1;
+    }
+    diff
+}
+
+/// Render test report entries as JSON, in the shape CI tooling expects:
+/// `{"tests": [{"path": ..., "success": ..., "failures": [...], ...}, ...]}`.
+fn render_json_report(entries: &[TestReportEntry]) -> String {
+    let mut json = String::from("{\n  \"tests\": [\n");
+    for (i, entry) in entries.iter().enumerate() {
+        json.push_str("    {\n");
+        let _ = writeln!(
+            json,
+            "      \"path\": \"{}\",",
+            json_escape(&entry.path.display().to_string())
+        );
+        let _ = writeln!(json, "      \"ignored\": {},", entry.ignored);
+        let _ = writeln!(json, "      \"success\": {},", entry.success);
+        let _ = writeln!(json, "      \"success_count\": {},", entry.success_count);
+        let _ = writeln!(json, "      \"total_count\": {},", entry.total_count);
+        let failures = entry
+            .failures
+            .iter()
+            .map(|f| format!("\"{}\"", json_escape(f)))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let _ = writeln!(json, "      \"failures\": [{}],", failures);
+        let _ = writeln!(
+            json,
+            "      \"saved_graph\": {},",
+            json_optional_path(&entry.saved_graph)
+        );
+        let _ = writeln!(
+            json,
+            "      \"saved_paths\": {},",
+            json_optional_path(&entry.saved_paths)
+        );
+        let _ = writeln!(
+            json,
+            "      \"saved_visualization\": {}",
+            json_optional_path(&entry.saved_visualization)
+        );
+        json.push_str(if i + 1 < entries.len() {
+            "    },\n"
+        } else {
+            "    }\n"
+        });
+    }
+    json.push_str("  ]\n}\n");
+    json
+}
+
+fn json_optional_path(path: &Option<PathBuf>) -> String {
+    match path {
+        Some(path) => format!("\"{}\"", json_escape(&path.display().to_string())),
+        None => "null".to_string(),
+    }
+}
+
+/// Escape a string for embedding in a JSON string literal, including the full
+/// control-character range (U+0000-U+001F): failure messages and paths are
+/// free-form and may contain newlines or other control characters, which would
+/// otherwise produce invalid JSON.
+fn json_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Render test report entries as a JUnit XML test suite, mapping each test file
+/// to a `<testcase>` and each assertion failure to a `<failure>`, so the report
+/// can be consumed by standard CI dashboards.
+fn render_junit_report(entries: &[TestReportEntry]) -> String {
+    let tests = entries.len();
+    let failures = entries.iter().filter(|e| !e.ignored && !e.success).count();
+    let skipped = entries.iter().filter(|e| e.ignored).count();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuite name=\"tree-sitter-stack-graphs\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+        tests, failures, skipped
+    );
+    for entry in entries {
+        let name = xml_escape(&entry.path.display().to_string());
+        if entry.ignored {
+            let _ = writeln!(xml, "  <testcase name=\"{}\">", name);
+            xml.push_str("    <skipped/>\n");
+            xml.push_str("  </testcase>\n");
+            continue;
+        }
+        if entry.failures.is_empty() {
+            let _ = writeln!(
+                xml,
+                "  <testcase name=\"{}\" assertions=\"{}\"/>",
+                name, entry.total_count
+            );
+        } else {
+            let _ = writeln!(
+                xml,
+                "  <testcase name=\"{}\" assertions=\"{}\">",
+                name, entry.total_count
+            );
+            for failure in &entry.failures {
+                let message = xml_escape(failure);
+                let _ = writeln!(xml, "    <failure message=\"{}\">{}</failure>", message, message);
+            }
+            xml.push_str("  </testcase>\n");
+        }
+    }
+    xml.push_str("</testsuite>\n");
+    xml
+}
+
+/// Escape a string for embedding in XML character data and attribute values.
+/// Control characters other than tab/newline/carriage-return are illegal in
+/// XML 1.0, even as numeric character references, so they are dropped rather
+/// than passed through.
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}